@@ -0,0 +1,127 @@
+// ELF-aware enumeration of a process' loaded shared objects, looking for
+// the structural fingerprints of an injected JVMTI/JNI agent rather than a
+// fixed byte signature: a JVMTI entry point exported from a library sitting
+// somewhere an attacker could have dropped it, or executable code with no
+// backing file on disk at all.
+//
+// This mirrors what Mozilla's crash-reporter `process_reader` linux backend
+// does with `goblin::elf::Elf::parse` over `/proc/<pid>/maps` mappings.
+
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use goblin::elf::Elf;
+use anyhow::Result;
+
+use crate::proc_maps::parse_maps;
+
+const JVMTI_ENTRY_SYMBOLS: &[&str] = &["Agent_OnAttach", "Agent_OnLoad"];
+
+/// HotSpot's JIT code cache is itself a large anonymous executable mapping,
+/// so anon-exec regions at or above this size are treated as the code cache
+/// rather than flagged — otherwise every healthy JVM trips this check.
+const LIKELY_CODE_CACHE_MIN_SIZE: usize = 1 << 20;
+
+#[derive(Debug)]
+pub struct SuspiciousModule {
+    pub pathname: Option<String>,
+    pub start: u64,
+    pub end: u64,
+    pub perms: String,
+    pub reason: String,
+}
+
+/// Scans every distinct file-backed executable mapping in `pid`'s address
+/// space (plus anonymous executable regions) and reports modules that look
+/// like an injected agent.
+pub fn scan_loaded_modules(pid: i32) -> Result<Vec<SuspiciousModule>> {
+    let mappings = parse_maps(pid)?;
+    let mut suspicious = Vec::new();
+    let mut seen_paths = std::collections::HashSet::new();
+
+    for mapping in &mappings {
+        if !mapping.is_executable() {
+            continue;
+        }
+
+        match &mapping.pathname {
+            None => {
+                if mapping.size() >= LIKELY_CODE_CACHE_MIN_SIZE {
+                    continue;
+                }
+
+                suspicious.push(SuspiciousModule {
+                    pathname: None,
+                    start: mapping.start,
+                    end: mapping.end,
+                    perms: mapping.perms.clone(),
+                    reason: "anonymous executable region with no backing file".to_string(),
+                });
+            }
+            Some(path) if is_special_path(path) => continue,
+            Some(path) => {
+                if !seen_paths.insert(path.clone()) {
+                    continue;
+                }
+
+                if let Some(reason) = inspect_module(path)? {
+                    suspicious.push(SuspiciousModule {
+                        pathname: Some(path.clone()),
+                        start: mapping.start,
+                        end: mapping.end,
+                        perms: mapping.perms.clone(),
+                        reason,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(suspicious)
+}
+
+/// `[heap]`, `[stack]`, `[vdso]` and friends aren't real files on disk.
+fn is_special_path(path: &str) -> bool {
+    path.starts_with('[') || path == "/dev/zero (deleted)"
+}
+
+fn inspect_module(path: &str) -> Result<Option<String>> {
+    if let Some(reason) = suspicious_location(path)? {
+        return Ok(Some(reason));
+    }
+
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(_) => return Ok(None),
+    };
+
+    let elf = match Elf::parse(&data) {
+        Ok(elf) => elf,
+        Err(_) => return Ok(None),
+    };
+
+    for sym in &elf.dynsyms {
+        let name = elf.dynstrtab.get_at(sym.st_name).unwrap_or("");
+        if JVMTI_ENTRY_SYMBOLS.contains(&name) {
+            return Ok(Some(format!("exports JVMTI entry point `{}`", name)));
+        }
+    }
+
+    Ok(None)
+}
+
+fn suspicious_location(path: &str) -> Result<Option<String>> {
+    const TMP_PREFIXES: &[&str] = &["/tmp/", "/dev/shm/", "/var/tmp/"];
+
+    if TMP_PREFIXES.iter().any(|p| path.starts_with(p)) {
+        return Ok(Some(format!("loaded from tmp-like directory {}", path)));
+    }
+
+    if let Ok(metadata) = fs::metadata(path) {
+        let mode = metadata.mode();
+        if mode & 0o002 != 0 {
+            return Ok(Some(format!("backing file {} is world-writable", path)));
+        }
+    }
+
+    Ok(None)
+}
@@ -0,0 +1,78 @@
+// Launch-time inspection of a JVM's `cmdline`, looking for agent flags
+// configured at process start rather than injected later at runtime. Modern
+// launchers (including modular JDKs, the way icedtea-web's rust-launcher
+// handles its MODULARJDK args) pass arguments indirectly through `@argfile`
+// response files, so those are expanded and re-scanned too.
+
+use std::collections::HashSet;
+use std::fs;
+use anyhow::Result;
+
+const AGENT_FLAGS: &[&str] = &["-javaagent:", "-agentlib:", "-agentpath:", "-Xbootclasspath"];
+
+#[derive(Debug)]
+pub struct LaunchFinding {
+    pub flag: String,
+    pub value: String,
+    pub source: String,
+}
+
+/// Scans `/proc/<pid>/cmdline` for JVM agent flags, expanding any `@<file>`
+/// argfile tokens and scanning their contents for the same flags.
+pub fn scan_launch_args(pid: i32) -> Result<Vec<LaunchFinding>> {
+    let proc = procfs::process::Process::new(pid)?;
+    let cmdline = proc.cmdline()?;
+
+    let mut findings = Vec::new();
+    let mut visited_argfiles = HashSet::new();
+    for token in &cmdline {
+        inspect_token(token, "cmdline", &mut findings, &mut visited_argfiles);
+    }
+
+    Ok(findings)
+}
+
+fn inspect_token(
+    token: &str,
+    source: &str,
+    findings: &mut Vec<LaunchFinding>,
+    visited_argfiles: &mut HashSet<String>,
+) {
+    if let Some(path) = token.strip_prefix('@') {
+        expand_argfile(path, findings, visited_argfiles);
+        return;
+    }
+
+    for flag in AGENT_FLAGS {
+        if let Some(value) = token.strip_prefix(flag) {
+            findings.push(LaunchFinding {
+                flag: flag.to_string(),
+                value: value.to_string(),
+                source: source.to_string(),
+            });
+        }
+    }
+}
+
+/// Best-effort: an argfile that can't be read (relative to a cwd we don't
+/// know, already cleaned up, permission-denied) is skipped rather than
+/// failing the whole scan. `visited_argfiles` guards against a self-
+/// referential or cyclic `@argfile` chain recursing forever.
+fn expand_argfile(
+    path: &str,
+    findings: &mut Vec<LaunchFinding>,
+    visited_argfiles: &mut HashSet<String>,
+) {
+    if !visited_argfiles.insert(path.to_string()) {
+        return;
+    }
+
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(_) => return,
+    };
+
+    for token in contents.split_whitespace() {
+        inspect_token(token, path, findings, visited_argfiles);
+    }
+}
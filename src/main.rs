@@ -4,21 +4,117 @@
 // See the LICENSE file for details.
 
 
+mod proc_maps;
+mod elf_scan;
+mod launch_scan;
+mod rules;
+
 use std::fs::File;
-use std::io::{BufRead, Read, Seek, SeekFrom};
+use std::io::{Read, Seek, SeekFrom};
+use std::os::raw::c_long;
+use std::path::PathBuf;
+use nix::errno::Errno;
 use nix::sys::ptrace;
 use nix::unistd::Pid;
 use anyhow::{Result, Context};
-use memmem::{Searcher, TwoWaySearcher};
 
 fn main() -> Result<()> {
-    let pid = find_java().context("Failed to find Java process")?;
-    println!("{}", pid);
-    scan_memory(pid)?;
+    let rules_path = parse_rules_flag();
+    let matchers = rules::load_rules(rules_path.as_deref()).context("Failed to load rules")?;
+
+    let candidates = find_java().context("Failed to find Java process")?;
+
+    for (pid, rss) in &candidates {
+        println!("{} ({} KiB RSS)", pid, rss / 1024);
+    }
+
+    for (pid, _) in &candidates {
+        if let Err(e) = scan_launch_args(*pid) {
+            eprintln!("[pid {}] launch-args scan failed: {}", pid, e);
+        }
+
+        if let Err(e) = scan_loaded_modules(*pid) {
+            eprintln!("[pid {}] module scan failed: {}", pid, e);
+        }
+
+        if let Err(e) = scan_memory(*pid, &matchers) {
+            eprintln!("[pid {}] scan failed: {}", pid, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a `--rules <path>` flag off the command line, pointing at a TOML
+/// rule file. Absent, `rules::load_rules` falls back to the built-in signatures.
+fn parse_rules_flag() -> Option<PathBuf> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--rules" {
+            return args.next().map(PathBuf::from);
+        }
+    }
+    None
+}
+
+fn scan_launch_args(pid: i32) -> Result<()> {
+    let findings = launch_scan::scan_launch_args(pid)?;
+
+    for finding in findings {
+        println!(
+            "[pid {}] [!] Agent flag {}{} (from {})",
+            pid, finding.flag, finding.value, finding.source
+        );
+    }
+
+    Ok(())
+}
+
+fn scan_loaded_modules(pid: i32) -> Result<()> {
+    let suspicious = elf_scan::scan_loaded_modules(pid)?;
+
+    if suspicious.is_empty() {
+        return Ok(());
+    }
+
+    for module in suspicious {
+        let label = module
+            .pathname
+            .unwrap_or_else(|| format!("[anon {} @ 0x{:x}]", module.perms, module.start));
+
+        println!(
+            "[pid {}] [!] Suspicious module {} (0x{:x}-0x{:x}): {}",
+            pid, label, module.start, module.end, module.reason
+        );
+    }
+
     Ok(())
 }
 
-fn find_java() -> Result<i32> {
+/// Reads `[start, end)` word-by-word via `PTRACE_PEEKDATA`, for regions
+/// where `/proc/<pid>/mem` is unreadable (guard pages, hardened kernels
+/// that restrict the mem file). Stops at the first `EIO`/`EFAULT`, keeping
+/// whatever whole words were read before it so partial coverage still
+/// feeds the signature matcher.
+fn read_region_ptrace(pid: Pid, start: u64, end: u64) -> Vec<u8> {
+    let word_size = std::mem::size_of::<c_long>() as u64;
+    let mut buffer = Vec::with_capacity((end - start) as usize);
+    let mut addr = start;
+
+    while addr < end {
+        Errno::clear();
+        match ptrace::read(pid, addr as *mut std::ffi::c_void) {
+            Ok(word) => buffer.extend_from_slice(&word.to_le_bytes()),
+            Err(Errno::EIO) | Err(Errno::EFAULT) => break,
+            Err(_) => break,
+        }
+        addr += word_size;
+    }
+
+    buffer
+}
+
+fn find_java() -> Result<Vec<(i32, u64)>> {
     let processes = procfs::process::all_processes()?;
     let mut java_candidates = Vec::new();
 
@@ -44,20 +140,13 @@ fn find_java() -> Result<i32> {
         return Err(anyhow::anyhow!("No Java processes found"));
     }
 
-    let (pid, _) = java_candidates.into_iter().max_by_key(|(_, rss)| *rss).unwrap();
-    Ok(pid)
+    Ok(java_candidates)
 }
 
-fn scan_memory(pid: i32) -> Result<()> {
+fn scan_memory(pid: i32, matchers: &[rules::Matcher]) -> Result<()> {
     let nix_pid = Pid::from_raw(pid);
     ptrace::attach(nix_pid)?;
 
-    const S1: [u32; 4] = [4242546329, 4601, 0, 0];
-    const S2: [u32; 4] = [4242546329, 505, 0, 0];
-
-    let s1: Vec<u8> = S1.iter().flat_map(|n| n.to_le_bytes()).collect();
-    let s2: Vec<u8> = S2.iter().flat_map(|n| n.to_le_bytes()).collect();
-
     loop {
         match nix::sys::wait::waitpid(nix_pid, None) {
             Ok(nix::sys::wait::WaitStatus::Stopped(_, _)) => break,
@@ -67,77 +156,77 @@ fn scan_memory(pid: i32) -> Result<()> {
     }
 
     let mut mem_file = File::open(format!("/proc/{}/mem", pid))?;
-    let maps_file = File::open(format!("/proc/{}/maps", pid))?;
-    let maps_reader = std::io::BufReader::new(maps_file);
-
-    let mut found_s1 = false;
-    let mut found_s2 = false;
+    let mappings = proc_maps::parse_maps(pid)?;
 
-    for line in maps_reader.lines() {
-        let line = line?;
+    let mut hits: Vec<Hit> = Vec::new();
 
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() < 2 { continue; }
+    for mapping in &mappings {
+        if !mapping.is_readable() { continue; }
 
-        let range = parts[0];
-        let perms = parts[1];
-
-        if !perms.contains('r') { continue; }
-
-        let addresses: Vec<&str> = range.split('-').collect();
-        if addresses.len() != 2 { continue; }
-
-        let start = match u64::from_str_radix(addresses[0], 16) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-        let end = match u64::from_str_radix(addresses[1], 16) {
-            Ok(v) => v,
-            Err(_) => continue,
-        };
-
-        let region_size = (end - start) as usize;
+        let region_size = mapping.size();
         if region_size == 0 { continue; }
 
         let mut buffer = vec![0u8; region_size];
 
-        if mem_file.seek(SeekFrom::Start(start)).is_err() {
-            continue;
-        }
-
-        if mem_file.read_exact(&mut buffer).is_err() {
-            continue;
-        }
+        let read_via_mem = mem_file.seek(SeekFrom::Start(mapping.start)).is_ok()
+            && mem_file.read_exact(&mut buffer).is_ok();
 
-        let search_s = |data: &[u8], sig: &[u8]| -> bool {
-            if sig.is_empty() || data.len() < sig.len() {
-                return false;
-            }
-            TwoWaySearcher::new(sig).search_in(data).is_some()
+        let buffer = if read_via_mem {
+            buffer
+        } else {
+            read_region_ptrace(nix_pid, mapping.start, mapping.end)
         };
 
-        if !found_s1 && search_s(&buffer, &s1) {
-            found_s1 = true;
+        if buffer.is_empty() {
+            continue;
         }
 
-        if !found_s1 && !found_s2 && search_s(&buffer, &s2) {
-            found_s2 = true;
-        }
+        for matcher in matchers {
+            if hits.iter().any(|h| h.label == matcher.label) { continue; }
+            if !matcher.permits(&mapping.perms) { continue; }
 
-        if found_s1 || found_s2 {
-            break;
+            if let Some(offset) = matcher.find_in(&buffer) {
+                hits.push(Hit {
+                    label: matcher.label.clone(),
+                    location: describe_location(mapping, offset as u64),
+                });
+            }
         }
     }
 
     ptrace::detach(nix_pid, None)?;
 
-    if found_s1 {
-        println!("[+] Injection detected (#S1).");
-    } else if found_s2 {
-        println!("[+] Injection detected (#S2).");
+    if hits.is_empty() {
+        println!("[pid {}] [-] No suspicious manipulations with JVM detected.", pid);
     } else {
-        println!("[-] No suspicious manipulations with JVM detected.");
+        for hit in &hits {
+            println!(
+                "[pid {}] [+] Injection detected (#{}) at {}",
+                pid, hit.label, hit.location
+            );
+        }
     }
 
     Ok(())
+}
+
+struct Hit {
+    label: String,
+    location: String,
+}
+
+/// Renders a hit's address as `module+0xoffset` for file-backed mappings,
+/// or `[anon <perms> @ 0x...]` for anonymous regions with no module to
+/// attribute the offset to. The file offset is taken from the mapping's
+/// own file-offset column plus the in-segment hit offset, so it lines up
+/// with the module's offsets even when its executable segment isn't
+/// mapped at file offset 0 — the same quantity `addr2line` expects.
+fn describe_location(mapping: &proc_maps::Mapping, offset: u64) -> String {
+    match &mapping.pathname {
+        // `[heap]`, `[stack]` and the like are anonymous too, not modules.
+        Some(path) if !path.starts_with('[') => {
+            format!("{}+0x{:x}", path, mapping.file_offset + offset)
+        }
+        _ => format!("[anon {} @ 0x{:x}]", mapping.perms, mapping.start + offset),
+    }
 }
\ No newline at end of file
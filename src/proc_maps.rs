@@ -0,0 +1,79 @@
+// Parsing of /proc/<pid>/maps into structured mappings, shared by the
+// memory scanner and the ELF-aware module enumeration.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use anyhow::Result;
+
+#[derive(Debug, Clone)]
+pub struct Mapping {
+    pub start: u64,
+    pub end: u64,
+    pub perms: String,
+    /// Byte offset into the backing file where this mapping starts (the
+    /// third `/proc/<pid>/maps` column), needed to turn an in-segment hit
+    /// offset into a true file/module offset when a library's executable
+    /// segment isn't mapped at file offset 0.
+    pub file_offset: u64,
+    pub pathname: Option<String>,
+}
+
+impl Mapping {
+    pub fn is_readable(&self) -> bool {
+        self.perms.contains('r')
+    }
+
+    pub fn is_executable(&self) -> bool {
+        self.perms.contains('x')
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.perms.contains('w')
+    }
+
+    pub fn size(&self) -> usize {
+        (self.end - self.start) as usize
+    }
+}
+
+/// Parses a single `/proc/<pid>/maps` line, e.g.
+/// `7f1234560000-7f1234580000 r-xp 00000000 08:01 131074 /lib/x86_64-linux-gnu/libc.so.6`
+pub fn parse_proc_maps_line(line: &str) -> Option<Mapping> {
+    let parts: Vec<&str> = line.splitn(6, char::is_whitespace).collect();
+    if parts.len() < 2 {
+        return None;
+    }
+
+    let addresses: Vec<&str> = parts[0].split('-').collect();
+    if addresses.len() != 2 {
+        return None;
+    }
+
+    let start = u64::from_str_radix(addresses[0], 16).ok()?;
+    let end = u64::from_str_radix(addresses[1], 16).ok()?;
+    let perms = parts[1].to_string();
+    let file_offset = parts.get(2).and_then(|s| u64::from_str_radix(s, 16).ok()).unwrap_or(0);
+
+    let pathname = parts
+        .get(5)
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+
+    Some(Mapping { start, end, perms, file_offset, pathname })
+}
+
+pub fn parse_maps(pid: i32) -> Result<Vec<Mapping>> {
+    let file = File::open(format!("/proc/{}/maps", pid))?;
+    let reader = BufReader::new(file);
+
+    let mut mappings = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if let Some(mapping) = parse_proc_maps_line(&line) {
+            mappings.push(mapping);
+        }
+    }
+
+    Ok(mappings)
+}
@@ -0,0 +1,132 @@
+// Loadable signature rules, replacing the old hardcoded `S1`/`S2` constants.
+// A rule is a byte pattern (optionally with `??` wildcard bytes) plus an
+// optional required-permission filter and a human-readable label. Rules are
+// compiled into `Matcher`s once at startup and then used for every mapping
+// `scan_memory` reads, so the scanner can be updated without a recompile.
+
+use std::fs;
+use std::path::Path;
+use anyhow::{Context, Result};
+use memmem::{Searcher, TwoWaySearcher};
+use serde::Deserialize;
+
+#[derive(Debug, Clone)]
+pub struct Matcher {
+    pub label: String,
+    pattern: Vec<Option<u8>>,
+    required_perms: Option<String>,
+}
+
+impl Matcher {
+    fn exact(label: &str, bytes: &[u8]) -> Self {
+        Matcher {
+            label: label.to_string(),
+            pattern: bytes.iter().map(|b| Some(*b)).collect(),
+            required_perms: None,
+        }
+    }
+
+    pub fn permits(&self, perms: &str) -> bool {
+        match &self.required_perms {
+            Some(required) => required.chars().all(|c| perms.contains(c)),
+            None => true,
+        }
+    }
+
+    /// Finds the first match of this rule's pattern in `data`. Patterns with
+    /// no wildcard bytes take the fast `TwoWaySearcher` exact-match path;
+    /// patterns with `??` bytes fall back to a masked window search that
+    /// skips over the wildcard positions, so a rule can tolerate relocated
+    /// addresses baked into an otherwise-fixed byte sequence.
+    pub fn find_in(&self, data: &[u8]) -> Option<usize> {
+        if self.pattern.is_empty() || data.len() < self.pattern.len() {
+            return None;
+        }
+
+        if let Some(exact) = self.exact_bytes() {
+            return TwoWaySearcher::new(&exact).search_in(data);
+        }
+
+        'windows: for offset in 0..=(data.len() - self.pattern.len()) {
+            for (i, expected) in self.pattern.iter().enumerate() {
+                if let Some(byte) = expected {
+                    if data[offset + i] != *byte {
+                        continue 'windows;
+                    }
+                }
+            }
+            return Some(offset);
+        }
+
+        None
+    }
+
+    fn exact_bytes(&self) -> Option<Vec<u8>> {
+        self.pattern.iter().copied().collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RuleFile {
+    rule: Vec<RawRule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawRule {
+    name: String,
+    /// Whitespace-separated hex bytes, e.g. `"d9 f9 52 fc ?? ??"`.
+    pattern: String,
+    perms: Option<String>,
+}
+
+fn parse_pattern(pattern: &str) -> Result<Vec<Option<u8>>> {
+    pattern
+        .split_whitespace()
+        .map(|tok| {
+            if tok == "??" {
+                Ok(None)
+            } else {
+                u8::from_str_radix(tok, 16)
+                    .map(Some)
+                    .with_context(|| format!("invalid pattern byte `{}`", tok))
+            }
+        })
+        .collect()
+}
+
+/// Loads rules from a TOML file of `[[rule]]` entries. Falls back to the
+/// built-in `S1`/`S2` signatures when `path` is `None`.
+pub fn load_rules(path: Option<&Path>) -> Result<Vec<Matcher>> {
+    let path = match path {
+        Some(path) => path,
+        None => return Ok(default_rules()),
+    };
+
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("failed to read rules file {}", path.display()))?;
+    let rule_file: RuleFile = toml::from_str(&contents)
+        .with_context(|| format!("failed to parse rules file {}", path.display()))?;
+
+    rule_file
+        .rule
+        .into_iter()
+        .map(|raw| {
+            Ok(Matcher {
+                label: raw.name,
+                pattern: parse_pattern(&raw.pattern)?,
+                required_perms: raw.perms,
+            })
+        })
+        .collect()
+}
+
+/// The two signatures `scan_memory` originally had baked in as `S1`/`S2`.
+fn default_rules() -> Vec<Matcher> {
+    const S1: [u32; 4] = [4242546329, 4601, 0, 0];
+    const S2: [u32; 4] = [4242546329, 505, 0, 0];
+
+    let s1: Vec<u8> = S1.iter().flat_map(|n| n.to_le_bytes()).collect();
+    let s2: Vec<u8> = S2.iter().flat_map(|n| n.to_le_bytes()).collect();
+
+    vec![Matcher::exact("S1", &s1), Matcher::exact("S2", &s2)]
+}